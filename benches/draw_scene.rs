@@ -0,0 +1,44 @@
+// CPU side of building the dot mesh for a full board. Uploading to the GPU
+// needs a window and isn't covered.
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ggez::graphics::{Color, DrawMode, MeshBuilder};
+use glam::Vec2;
+use pacman_rust::DotBatch;
+
+// Same layout as a fresh round: every cell center inside the outer ring
+fn full_board() -> Vec<Vec2> {
+    (1..19)
+        .flat_map(|x| (1..19).map(move |y| Vec2::new(x as f32 * 30.0, y as f32 * 30.0)))
+        .collect()
+}
+
+fn dot_mesh(c: &mut Criterion) {
+    let dots = full_board();
+    let mut group = c.benchmark_group("dot_mesh");
+
+    // How every frame built the dots before, one fresh builder per frame
+    group.bench_function("mesh_builder", |b| {
+        b.iter(|| {
+            let mut builder = MeshBuilder::new();
+            for dot in black_box(&dots) {
+                builder
+                    .circle(DrawMode::fill(), [dot.x, dot.y], 6.0, 0.1, Color::WHITE)
+                    .unwrap();
+            }
+            black_box(builder.build().indices.len())
+        })
+    });
+
+    let mut batch = DotBatch::default();
+    group.bench_function("dot_batch", |b| {
+        b.iter(|| {
+            batch.rebuild(black_box(&dots), |_| 1.0);
+            black_box(batch.mesh_data().indices.len())
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, dot_mesh);
+criterion_main!(benches);
@@ -0,0 +1,1854 @@
+//! A simple Pacman clone made with ggez, runnable with a custom `GameConfig`
+
+use ggez::{
+    event,
+    graphics::{self, Color, DrawMode, DrawParam, LinearColor, Mesh, MeshBuilder, MeshData, Vertex, Drawable},
+    input::gamepad::{gilrs::Button, GamepadId},
+    input::keyboard::{KeyCode, KeyInput},
+    input::mouse::MouseButton,
+    Context, GameResult,
+};
+use glam::Vec2;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+const GRID_SIZE: i32 = 20;
+const CELL_SIZE: f32 = 30.0;
+const BOARD_SIZE: f32 = CELL_SIZE * GRID_SIZE as f32;
+const PACMAN_SPEED: f32 = 5.0;
+const SCREEN_WIDTH: f32 = 800.0;
+const SCREEN_HEIGHT: f32 = 600.0;
+const MOUTH_SPEED: f32 = 0.2;
+const MAX_MOUTH_ANGLE: f32 = 1.0; // Increased from 0.7 to 1.0 (about 57 degrees)
+const DOT_POINTS: i32 = 10;
+const DOT_SEGMENTS: u32 = 16; // Rim vertices per dot, enough for a dot this small to look round
+const CORNER_CUT_DISTANCE: f32 = CELL_SIZE * 0.25;
+const PRACTICE_SNAPSHOT_FRAMES: u64 = 60; // Frames between practice-mode snapshots
+const PRACTICE_HISTORY_LEN: usize = 10;
+const DOT_INTRO_FRAMES: u64 = 90; // Update steps before play starts
+const DOT_INTRO_DURATION: f32 = 1.5; // Seconds for the pop-in animation, purely visual
+const IDLE_BOB_SPEED: f32 = 6.0; // Radians per second
+const IDLE_BOB_HEIGHT: f32 = CELL_SIZE * 0.15;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn to_vec2(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0.0, -1.0),
+            Direction::Down => Vec2::new(0.0, 1.0),
+            Direction::Left => Vec2::new(-1.0, 0.0),
+            Direction::Right => Vec2::new(1.0, 0.0),
+        }
+    }
+
+    fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EdgeBehavior {
+    Clamp,
+    Wrap, // Tunnel through to the opposite edge
+    Bounce, // Reverse direction
+}
+
+// What happens when Pacman reaches each edge of the board
+#[derive(Copy, Clone, Debug)]
+pub struct Edges {
+    pub top: EdgeBehavior,
+    pub bottom: EdgeBehavior,
+    pub left: EdgeBehavior,
+    pub right: EdgeBehavior,
+}
+
+impl Edges {
+    fn wraps_in_pairs(&self) -> bool {
+        matches!(self.left, EdgeBehavior::Wrap) == matches!(self.right, EdgeBehavior::Wrap)
+            && matches!(self.top, EdgeBehavior::Wrap) == matches!(self.bottom, EdgeBehavior::Wrap)
+    }
+}
+
+// Resolves a coordinate that left the playable range along one axis.
+// Returns the corrected coordinate and whether the movement should reverse.
+fn resolve_edge(value: f32, low: EdgeBehavior, high: EdgeBehavior) -> (f32, bool) {
+    let min = CELL_SIZE;
+    let max = CELL_SIZE * (GRID_SIZE - 1) as f32;
+    let (edge, bound, opposite) = if value < min {
+        (low, min, max)
+    } else if value > max {
+        (high, max, min)
+    } else {
+        return (value, false);
+    };
+
+    match edge {
+        EdgeBehavior::Clamp => (bound, false),
+        EdgeBehavior::Wrap => (opposite, false),
+        EdgeBehavior::Bounce => (bound, true),
+    }
+}
+
+// Nearest cell center coordinate along one axis
+fn snap_to_cell(value: f32) -> f32 {
+    (value / CELL_SIZE).round() * CELL_SIZE
+}
+
+fn approach(value: f32, target: f32, max_step: f32) -> f32 {
+    value + (target - value).clamp(-max_step, max_step)
+}
+
+// How scores are rendered in the HUD and on the victory screen
+#[derive(Copy, Clone, Debug)]
+pub enum ScoreFormat {
+    Plain,
+    ZeroPadded(usize), // Minimum digit count, e.g. 6 renders 1230 as 001230
+    Grouped(char), // Thousands separator, e.g. ',' renders 12300 as 12,300
+}
+
+impl ScoreFormat {
+    fn format(self, score: i32) -> String {
+        match self {
+            ScoreFormat::Plain => score.to_string(),
+            ScoreFormat::ZeroPadded(width) => format!("{:0width$}", score, width = width),
+            ScoreFormat::Grouped(separator) => {
+                let digits = score.to_string();
+                let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+                for (i, ch) in digits.chars().enumerate() {
+                    let remaining = digits.len() - i;
+                    if i > 0 && remaining % 3 == 0 {
+                        grouped.push(separator);
+                    }
+                    grouped.push(ch);
+                }
+                grouped
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum VictoryStyle {
+    Minimal, // Title, final score and Play Again
+    Stats, // Adds a summary of the round below the final score
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum MenuAction {
+    Confirm,
+    Cancel,
+}
+
+impl MenuAction {
+    fn from_key(keycode: KeyCode, config: &GameConfig) -> Option<Self> {
+        if config.confirm_keys.contains(&keycode) {
+            Some(MenuAction::Confirm)
+        } else if config.cancel_keys.contains(&keycode) {
+            Some(MenuAction::Cancel)
+        } else {
+            None
+        }
+    }
+
+    fn from_button(button: Button, config: &GameConfig) -> Option<Self> {
+        if config.confirm_buttons.contains(&button) {
+            Some(MenuAction::Confirm)
+        } else if config.cancel_buttons.contains(&button) {
+            Some(MenuAction::Cancel)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct DirectionController {
+    queued_direction: Option<Direction>,
+    current_direction: Option<Direction>,
+    corner_cutting: bool,
+}
+
+impl DirectionController {
+    fn new(corner_cutting: bool) -> Self {
+        Self {
+            queued_direction: None,
+            current_direction: None,
+            corner_cutting,
+        }
+    }
+
+    fn queue_direction(&mut self, new_direction: Direction) {
+        self.queued_direction = Some(new_direction);
+    }
+
+    fn update(&mut self, position: Vec2) -> Option<Direction> {
+        if let Some(queued) = self.queued_direction {
+            if self.is_aligned_with_grid(position) || self.can_cut_corner(queued, position) {
+                self.current_direction = Some(queued);
+                self.queued_direction = None;
+            }
+        }
+        self.current_direction
+    }
+
+    fn reverse(&mut self) {
+        self.current_direction = self.current_direction.map(Direction::opposite);
+    }
+
+    fn can_cut_corner(&self, queued: Direction, position: Vec2) -> bool {
+        let Some(current) = self.current_direction else {
+            return false;
+        };
+        if !self.corner_cutting || current.is_horizontal() == queued.is_horizontal() {
+            return false;
+        }
+
+        let along = if current.is_horizontal() { position.x } else { position.y };
+        (along - snap_to_cell(along)).abs() <= CORNER_CUT_DISTANCE
+    }
+
+    fn is_aligned_with_grid(&self, position: Vec2) -> bool {
+        let cell_x = position.x / CELL_SIZE;
+        let cell_y = position.y / CELL_SIZE;
+        
+        (cell_x.fract() < 0.1 || cell_x.fract() > 0.9) && 
+        (cell_y.fract() < 0.1 || cell_y.fract() > 0.9)
+    }
+}
+
+#[derive(Clone)]
+struct GameObject {
+    pos: Vec2,
+    direction: Vec2,
+    size: f32,
+    mouth_angle: f32,
+    mouth_opening: bool,
+}
+
+// Elapsed seconds. `active` and `level` only advance during play; `level` restarts with each round
+#[derive(Copy, Clone, Debug, Default)]
+struct Clock {
+    total: f32,
+    active: f32,
+    level: f32,
+}
+
+impl Clock {
+    fn advance(&mut self, dt: f32, playing: bool) {
+        self.total += dt;
+        if playing {
+            self.active += dt;
+            self.level += dt;
+        }
+    }
+
+    fn start_level(&mut self) {
+        self.level = 0.0;
+    }
+}
+
+// The parts of the game state that practice mode can rewind to
+#[derive(Clone)]
+struct Snapshot {
+    pacman: GameObject,
+    dots: Vec<Vec2>,
+    score: i32,
+    direction_controller: DirectionController,
+    round_time: f32, // Clock level time, so rewound play doesn't count toward the round
+}
+
+/// One frame's dots as a single mesh. The buffers are kept between
+/// rebuilds, so redrawing the board doesn't allocate once they have grown.
+#[derive(Default)]
+pub struct DotBatch {
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+}
+
+impl DotBatch {
+    /// Refills the batch with a fan per dot, scaled and faded in by `reveal(index)`
+    pub fn rebuild(&mut self, dots: &[Vec2], reveal: impl Fn(usize) -> f32) {
+        self.vertices.clear();
+        self.indices.clear();
+        for (index, dot) in dots.iter().enumerate() {
+            let reveal = reveal(index);
+            if reveal <= 0.0 {
+                continue;
+            }
+            let radius = CELL_SIZE * 0.2 * reveal;
+            let color = LinearColor::from(Color::new(1.0, 1.0, 1.0, reveal)).into();
+            let center = self.vertices.len() as u32;
+            self.vertices.push(Vertex { position: [dot.x, dot.y], uv: [0.0, 0.0], color });
+            for i in 0..DOT_SEGMENTS {
+                let angle = i as f32 * std::f32::consts::TAU / DOT_SEGMENTS as f32;
+                self.vertices.push(Vertex {
+                    position: [dot.x + radius * angle.cos(), dot.y + radius * angle.sin()],
+                    uv: [0.0, 0.0],
+                    color,
+                });
+                let next = (i + 1) % DOT_SEGMENTS;
+                self.indices.extend([center, center + 1 + i, center + 1 + next]);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn mesh_data(&self) -> MeshData<'_> {
+        MeshData {
+            vertices: &self.vertices,
+            indices: &self.indices,
+        }
+    }
+}
+
+struct GameState {
+    config: GameConfig,
+    pacman: GameObject,
+    dots: Vec<Vec2>,
+    total_dots: usize,
+    dots_required: usize,
+    score: i32,
+    direction_controller: DirectionController,
+    game_won: bool,
+    quit_pending: bool, // Cancel was pressed once on the victory overlay
+    won_at: f32, // Clock total when the game was won
+    grid_mesh: Option<Mesh>, // Built on first draw, the grid never changes
+    dot_batch: DotBatch,
+    practice_mode: bool,
+    history: VecDeque<Snapshot>,
+    last_snapshot_frame: u64,
+    round_start_frame: u64,
+    clock: Clock,
+    frame: u64, // Update steps since launch, never reset or rewound
+    view_scale: f32, // Below 1.0 when the board is shrunk to fit a small window
+    #[cfg(feature = "debug")]
+    show_debug: bool,
+    #[cfg(feature = "debug")]
+    visits: Vec<u32>, // Frames Pacman has spent on each cell, row-major
+}
+
+impl GameState {
+    fn new(config: GameConfig) -> Self {
+        let dots = Self::spawn_dots();
+        let total_dots = dots.len();
+
+        GameState {
+            dots_required: Self::dots_required(total_dots, config.win_dot_fraction),
+            score: config.starting_score.max(0),
+            direction_controller: DirectionController::new(config.corner_cutting),
+            config,
+            pacman: GameObject {
+                pos: Vec2::new(CELL_SIZE, GRID_SIZE as f32 * CELL_SIZE / 2.0),
+                direction: Vec2::new(0.0, 0.0),
+                size: CELL_SIZE * 0.8,
+                mouth_angle: 0.0,
+                mouth_opening: true,
+            },
+            dots,
+            total_dots,
+            game_won: false,
+            quit_pending: false,
+            won_at: 0.0,
+            grid_mesh: None,
+            dot_batch: DotBatch::default(),
+            practice_mode: false,
+            history: VecDeque::with_capacity(PRACTICE_HISTORY_LEN),
+            last_snapshot_frame: 0,
+            round_start_frame: 0,
+            clock: Clock::default(),
+            frame: 0,
+            view_scale: 1.0,
+            #[cfg(feature = "debug")]
+            show_debug: false,
+            #[cfg(feature = "debug")]
+            visits: vec![0; (GRID_SIZE * GRID_SIZE) as usize],
+        }
+    }
+
+    // Create dots in a grid pattern
+    fn spawn_dots() -> Vec<Vec2> {
+        let mut dots = Vec::new();
+        for x in 1..GRID_SIZE-1 {
+            for y in 1..GRID_SIZE-1 {
+                dots.push(Vec2::new(
+                    x as f32 * CELL_SIZE,
+                    y as f32 * CELL_SIZE,
+                ));
+            }
+        }
+        dots
+    }
+
+    fn dots_required(total_dots: usize, win_dot_fraction: f32) -> usize {
+        let required = (total_dots as f32 * win_dot_fraction).ceil() as usize;
+        required.clamp(1, total_dots.max(1))
+    }
+
+    fn dots_eaten(&self) -> usize {
+        self.total_dots - self.dots.len()
+    }
+
+    fn reset(&mut self) {
+        // Reset dots
+        self.dots = Self::spawn_dots();
+        self.total_dots = self.dots.len();
+        self.dots_required = Self::dots_required(self.total_dots, self.config.win_dot_fraction);
+
+        // Reset pacman
+        self.pacman.pos = Vec2::new(CELL_SIZE, GRID_SIZE as f32 * CELL_SIZE / 2.0);
+        self.pacman.direction = Vec2::new(0.0, 0.0);
+        
+        // Reset score and game state
+        self.score = self.config.starting_score.max(0);
+        self.game_won = false;
+        self.quit_pending = false;
+        self.clock.start_level();
+        self.round_start_frame = self.frame;
+        self.direction_controller = DirectionController::new(self.config.corner_cutting);
+        self.pacman.mouth_angle = 0.0;
+        self.pacman.mouth_opening = true;
+
+        // Rewinding never crosses a restart
+        self.history.clear();
+        if self.practice_mode {
+            self.push_snapshot();
+        }
+
+        #[cfg(feature = "debug")]
+        self.visits.fill(0);
+    }
+
+    fn toggle_practice_mode(&mut self) {
+        self.practice_mode = !self.practice_mode;
+        self.history.clear();
+        if self.practice_mode {
+            self.push_snapshot();
+        }
+    }
+
+    fn push_snapshot(&mut self) {
+        if self.history.len() == PRACTICE_HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(Snapshot {
+            pacman: self.pacman.clone(),
+            dots: self.dots.clone(),
+            score: self.score,
+            direction_controller: self.direction_controller.clone(),
+            round_time: self.clock.level,
+        });
+        self.last_snapshot_frame = self.frame;
+    }
+
+    // Restores the most recent snapshot; repeated rewinds step further back
+    fn rewind(&mut self) {
+        if !self.practice_mode {
+            return;
+        }
+        if let Some(snapshot) = self.history.pop_back() {
+            self.pacman = snapshot.pacman;
+            self.dots = snapshot.dots;
+            self.score = snapshot.score;
+            self.direction_controller = snapshot.direction_controller;
+            self.clock.level = snapshot.round_time;
+            // won_at is left stale; it's only read while game_won and is set again on the next win
+            self.game_won = false;
+            self.quit_pending = false;
+            self.last_snapshot_frame = self.frame;
+        }
+    }
+
+    fn update(&mut self, dt: f32) {
+        self.frame += 1;
+        self.clock.advance(dt, !self.game_won);
+        if self.game_won {
+            return;  // Don't update game if won
+        }
+        if self.in_intro() {
+            return;  // Movement and collection wait for the dots to appear
+        }
+
+        // Scheduled by frame rather than dt so snapshots land on the same steps every run
+        if self.practice_mode && self.frame - self.last_snapshot_frame >= PRACTICE_SNAPSHOT_FRAMES {
+            self.push_snapshot();
+        }
+
+        // Update direction based on grid alignment
+        if let Some(direction) = self.direction_controller.update(self.pacman.pos) {
+            self.pacman.direction = direction.to_vec2();
+        }
+
+        // Update pacman position
+        self.pacman.pos += self.pacman.direction * PACMAN_SPEED;
+
+        // After a corner cut, drift back onto the lane while moving along the new axis
+        if self.pacman.direction.x != 0.0 {
+            self.pacman.pos.y = approach(self.pacman.pos.y, snap_to_cell(self.pacman.pos.y), PACMAN_SPEED);
+        } else if self.pacman.direction.y != 0.0 {
+            self.pacman.pos.x = approach(self.pacman.pos.x, snap_to_cell(self.pacman.pos.x), PACMAN_SPEED);
+        }
+        
+        // Keep pacman within bounds, or wrap/bounce per edge
+        let edges = self.config.edges;
+        let (x, bounced_x) = resolve_edge(self.pacman.pos.x, edges.left, edges.right);
+        let (y, bounced_y) = resolve_edge(self.pacman.pos.y, edges.top, edges.bottom);
+        self.pacman.pos = Vec2::new(x, y);
+        if bounced_x || bounced_y {
+            self.direction_controller.reverse();
+            self.pacman.direction = -self.pacman.direction;
+        }
+
+        #[cfg(feature = "debug")]
+        self.record_visit();
+
+        // Collect dots
+        let pacman_pos = self.pacman.pos;
+        let dots_before = self.dots.len();
+        self.dots.retain(|dot| (*dot - pacman_pos).length() >= CELL_SIZE * 0.5);
+        let eaten = i32::try_from(dots_before - self.dots.len()).unwrap_or(i32::MAX);
+        self.add_score(DOT_POINTS.saturating_mul(eaten));
+
+        // Check for victory condition
+        if self.dots_eaten() >= self.dots_required {
+            self.game_won = true;
+            self.won_at = self.clock.total;
+            // Freeze in a closed pose rather than wherever the animation was
+            self.pacman.mouth_angle = 0.0;
+            self.pacman.mouth_opening = true;
+            return;
+        }
+
+        // Update mouth animation
+        if self.pacman.direction.length() > 0.0 {
+            if self.pacman.mouth_opening {
+                self.pacman.mouth_angle += MOUTH_SPEED;
+                if self.pacman.mouth_angle >= MAX_MOUTH_ANGLE {
+                    self.pacman.mouth_opening = false;
+                }
+            } else {
+                self.pacman.mouth_angle -= MOUTH_SPEED;
+                if self.pacman.mouth_angle <= 0.0 {
+                    self.pacman.mouth_opening = true;
+                }
+            }
+        } else {
+            // Reset mouth when not moving
+            self.pacman.mouth_angle = 0.0;
+            self.pacman.mouth_opening = true;
+        }
+    }
+
+    // Idle animation only runs while no direction has been committed
+    fn idle_animating(&self) -> bool {
+        self.config.idle_animation
+            && !self.config.reduce_motion
+            && self.direction_controller.current_direction.is_none()
+    }
+
+    // Idling only happens before the first direction of a round, so the round clock is the idle time
+    fn idle_bob_offset(&self) -> f32 {
+        if !self.idle_animating() {
+            return 0.0;
+        }
+        -(self.clock.level * IDLE_BOB_SPEED).sin().abs() * IDLE_BOB_HEIGHT
+    }
+
+    // Gated on frames rather than the clock so the first playable step doesn't depend on frame timing
+    fn in_intro(&self) -> bool {
+        self.config.dot_intro
+            && !self.config.reduce_motion
+            && self.frame - self.round_start_frame <= DOT_INTRO_FRAMES
+    }
+
+    // How far the dot at `index` is through its pop-in, from 0.0 to 1.0.
+    // Dots start one after another over the first 70% of the intro.
+    fn dot_reveal(&self, index: usize) -> f32 {
+        if !self.in_intro() {
+            return 1.0;
+        }
+        let progress = self.clock.level / DOT_INTRO_DURATION;
+        let start = 0.7 * index as f32 / self.dots.len() as f32;
+        ((progress - start) / 0.3).clamp(0.0, 1.0)
+    }
+
+    // Fraction of the way through the overlay fade-in, from 0.0 to 1.0
+    fn overlay_fade(&self) -> f32 {
+        let duration = self.config.overlay_fade_duration;
+        if self.config.reduce_motion || duration <= 0.0 {
+            return 1.0;
+        }
+        ((self.clock.total - self.won_at) / duration).min(1.0)
+    }
+
+    // Saturates instead of overflowing and never lets the score drop below zero
+    fn add_score(&mut self, points: i32) {
+        self.score = self.score.saturating_add(points).max(0);
+    }
+
+    fn draw_grid(&mut self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
+        if let Some(grid_mesh) = &self.grid_mesh {
+            canvas.draw(grid_mesh, DrawParam::default());
+            return Ok(());
+        }
+
+        let mut mesh_builder = MeshBuilder::new();
+        
+        // Draw vertical lines
+        for x in (0..=(SCREEN_WIDTH as i32)).step_by(CELL_SIZE as usize) {
+            mesh_builder.line(
+                &[
+                    [x as f32, 0.0],
+                    [x as f32, SCREEN_HEIGHT],
+                ],
+                1.0,
+                Color::new(0.3, 0.3, 0.3, 1.0), // Grey color
+            )?;
+        }
+
+        // Draw horizontal lines
+        for y in (0..=(SCREEN_HEIGHT as i32)).step_by(CELL_SIZE as usize) {
+            mesh_builder.line(
+                &[
+                    [0.0, y as f32],
+                    [SCREEN_WIDTH, y as f32],
+                ],
+                1.0,
+                Color::new(0.3, 0.3, 0.3, 1.0), // Grey color
+            )?;
+        }
+
+        let grid_mesh = graphics::Mesh::from_data(ctx, mesh_builder.build());
+        canvas.draw(&grid_mesh, DrawParam::default());
+        self.grid_mesh = Some(grid_mesh);
+        
+        Ok(())
+    }
+
+    // Applies an overlay action and returns whether the game should quit.
+    // The victory overlay is the only menu; its single button always has focus.
+    fn handle_menu_action(&mut self, action: MenuAction) -> bool {
+        if !self.game_won {
+            return false;
+        }
+        match action {
+            MenuAction::Confirm => {
+                self.reset();
+                false
+            }
+            // A single stray press, e.g. of a gamepad's B button, shouldn't close the game
+            MenuAction::Cancel => {
+                if self.quit_pending {
+                    return true;
+                }
+                self.quit_pending = true;
+                false
+            }
+        }
+    }
+
+    // Summary lines for the stats victory screen
+    fn stats_summary(&self) -> Vec<String> {
+        vec![
+            format!("Time: {:.1}s", self.clock.level),
+            format!("Dots eaten: {}/{}", self.dots_eaten(), self.total_dots),
+        ]
+    }
+
+    // Text lines of the victory overlay, title first
+    fn victory_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            "You Won!".to_string(),
+            format!("Final Score: {}", self.config.score_format.format(self.score)),
+        ];
+        if self.config.victory_style == VictoryStyle::Stats {
+            lines.extend(self.stats_summary());
+        }
+        lines
+    }
+
+    fn hud_line_position(&self, line: usize) -> [f32; 2] {
+        let [x, y] = self.config.hud_anchor;
+        [x, y + line as f32 * 20.0 * self.config.hud_scale]
+    }
+
+    fn draw_hud_line(&self, canvas: &mut graphics::Canvas, text: &str, line: usize, color: Color) {
+        let mut text = graphics::Text::new(text);
+        text.set_scale(16.0 * self.config.hud_scale);
+        canvas.draw(
+            &text,
+            DrawParam::default()
+                .color(color)
+                .dest(self.hud_line_position(line)),
+        );
+    }
+
+    fn draw_centered_text(
+        &self,
+        ctx: &Context,
+        canvas: &mut graphics::Canvas,
+        text: &str,
+        y: f32,
+        color: Color,
+    ) {
+        let text = graphics::Text::new(text);
+        let width = text.dimensions(ctx).map_or(0.0, |dims| dims.w);
+        canvas.draw(
+            &text,
+            DrawParam::default()
+                .color(color)
+                .dest([SCREEN_WIDTH * 0.5 - width * 0.5, y]),
+        );
+    }
+
+    // Scales the board to the window it actually got, which may be smaller than requested.
+    // Empty sizes, e.g. from a minimized window, keep the previous scale.
+    fn fit_to_window(&mut self, width: f32, height: f32) {
+        if width <= 0.0 || height <= 0.0 {
+            return;
+        }
+        self.view_scale = match self.config.window_fit {
+            WindowFit::ScaleToFit => board_fit_scale(width, height),
+            WindowFit::EnforceMinimum => 1.0,
+        };
+    }
+
+    // Restarts when the Play Again button is clicked on the victory overlay
+    fn handle_click(&mut self, window_pos: Vec2) {
+        if !self.game_won {
+            return;
+        }
+        // Clicks arrive in window pixels, the button is laid out in board coordinates
+        let point = window_pos / self.view_scale;
+
+        // Check if click is within Play Again button bounds
+        let button_width = 200.0;
+        let button_height = 50.0;
+        let button_x = SCREEN_WIDTH * 0.5 - button_width * 0.5;
+        let button_y = SCREEN_HEIGHT * 0.6;
+
+        if self.is_point_in_rect(
+            point,
+            Vec2::new(button_x, button_y),
+            Vec2::new(button_width, button_height),
+        ) {
+            self.reset();
+        }
+    }
+
+    fn is_point_in_rect(&self, point: Vec2, rect_pos: Vec2, rect_size: Vec2) -> bool {
+        point.x >= rect_pos.x 
+            && point.x <= rect_pos.x + rect_size.x 
+            && point.y >= rect_pos.y 
+            && point.y <= rect_pos.y + rect_size.y
+    }
+}
+
+#[cfg(feature = "debug")]
+impl GameState {
+    fn cell_index(pos: Vec2) -> Option<usize> {
+        let x = (pos.x / CELL_SIZE).round() as i32;
+        let y = (pos.y / CELL_SIZE).round() as i32;
+        if (0..GRID_SIZE).contains(&x) && (0..GRID_SIZE).contains(&y) {
+            Some((y * GRID_SIZE + x) as usize)
+        } else {
+            None
+        }
+    }
+
+    // One-line summary of the live state, for attaching to bug reports
+    fn debug_line(&self) -> String {
+        format!(
+            "frame {} | round {:.2}s | score {} | dots {}/{} | pos ({:.0}, {:.0}) | practice {}",
+            self.frame,
+            self.clock.level,
+            self.score,
+            self.dots_eaten(),
+            self.dots_required,
+            self.pacman.pos.x,
+            self.pacman.pos.y,
+            if self.practice_mode { "on" } else { "off" },
+        )
+    }
+
+    fn record_visit(&mut self) {
+        if let Some(index) = Self::cell_index(self.pacman.pos) {
+            self.visits[index] = self.visits[index].saturating_add(1);
+        }
+    }
+
+    // Tints each cell by how long Pacman has spent on it, relative to the busiest cell
+    fn draw_heatmap(&self, ctx: &mut Context, canvas: &mut graphics::Canvas) -> GameResult {
+        let max_visits = self.visits.iter().copied().max().unwrap_or(0);
+        if max_visits == 0 {
+            return Ok(());
+        }
+
+        let mut mesh_builder = MeshBuilder::new();
+        for (index, &count) in self.visits.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let x = (index as i32 % GRID_SIZE) as f32 * CELL_SIZE;
+            let y = (index as i32 / GRID_SIZE) as f32 * CELL_SIZE;
+            mesh_builder.rectangle(
+                DrawMode::fill(),
+                graphics::Rect::new(x - CELL_SIZE * 0.5, y - CELL_SIZE * 0.5, CELL_SIZE, CELL_SIZE),
+                Color::new(1.0, 0.2, 0.0, 0.4 * count as f32 / max_visits as f32),
+            )?;
+        }
+        let heatmap_mesh = graphics::Mesh::from_data(ctx, mesh_builder.build());
+        canvas.draw(&heatmap_mesh, DrawParam::default());
+        Ok(())
+    }
+}
+
+impl event::EventHandler for GameState {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        self.update(ctx.time.delta().as_secs_f32());
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        let mut canvas = graphics::Canvas::from_frame(ctx, Color::BLACK);
+        if self.view_scale < 1.0 {
+            let (width, height) = ctx.gfx.drawable_size();
+            canvas.set_screen_coordinates(graphics::Rect::new(
+                0.0,
+                0.0,
+                width / self.view_scale,
+                height / self.view_scale,
+            ));
+        }
+
+        // Draw grid first (so it's behind everything else)
+        self.draw_grid(ctx, &mut canvas)?;
+
+        #[cfg(feature = "debug")]
+        if self.show_debug {
+            self.draw_heatmap(ctx, &mut canvas)?;
+        }
+
+        // Draw dots, batched into a single mesh
+        let mut dot_batch = std::mem::take(&mut self.dot_batch);
+        dot_batch.rebuild(&self.dots, |index| self.dot_reveal(index));
+        if !dot_batch.is_empty() {
+            let dots_mesh = graphics::Mesh::from_data(ctx, dot_batch.mesh_data());
+            canvas.draw(&dots_mesh, DrawParam::default());
+        }
+        self.dot_batch = dot_batch;
+
+        // Draw Pacman
+        let mut mesh_builder = MeshBuilder::new();
+        
+        // Calculate rotation angle based on direction
+        let rotation = if self.pacman.direction.length() > 0.0 {
+            self.pacman.direction.y.atan2(self.pacman.direction.x)
+        } else {
+            0.0 // Face right when not moving
+        };
+
+        // Draw Pacman body (a pie shape)
+        let mesh_data = mesh_builder
+            .circle(
+                DrawMode::fill(),
+                [0.0, 0.0],  // Center at origin for rotation
+                self.pacman.size * 0.5,
+                0.1,
+                Color::YELLOW,
+            )?
+            .build();
+        
+        let pacman_mesh = graphics::Mesh::from_data(ctx, mesh_data);
+        
+        // Draw the pie-shaped mouth cutout (both sides)
+        let mouth_mesh = Mesh::new_polygon(
+            ctx,
+            DrawMode::fill(),
+            &[
+                [0.0, 0.0],
+                [self.pacman.size * 0.5, -self.pacman.size * 0.5 * self.pacman.mouth_angle.sin()],
+                [self.pacman.size * 0.5, self.pacman.size * 0.5 * self.pacman.mouth_angle.sin()],
+            ],
+            Color::BLACK,
+        )?;
+
+        // Draw Pacman with proper positioning and rotation
+        let pacman_dest = [self.pacman.pos.x, self.pacman.pos.y + self.idle_bob_offset()];
+        canvas.draw(
+            &pacman_mesh,
+            DrawParam::default()
+                .dest(pacman_dest)
+                .rotation(rotation)
+        );
+        
+        canvas.draw(
+            &mouth_mesh,
+            DrawParam::default()
+                .dest(pacman_dest)
+                .rotation(rotation)
+        );
+
+        // Draw score
+        let score_line = if self.dots_required < self.total_dots {
+            format!(
+                "Score: {}  Dots: {}/{}",
+                self.config.score_format.format(self.score),
+                self.dots_eaten(),
+                self.dots_required
+            )
+        } else {
+            format!("Score: {}", self.config.score_format.format(self.score))
+        };
+        self.draw_hud_line(&mut canvas, &score_line, 0, Color::WHITE);
+
+        if self.practice_mode {
+            self.draw_hud_line(&mut canvas, "Practice (Backspace to rewind)", 1, Color::YELLOW);
+        }
+
+        #[cfg(feature = "debug")]
+        if self.show_debug {
+            let line = if self.practice_mode { 2 } else { 1 };
+            self.draw_hud_line(&mut canvas, &self.debug_line(), line, Color::new(0.6, 1.0, 0.6, 1.0));
+        }
+
+        // Draw victory overlay if game is won
+        if self.game_won {
+            let fade = self.overlay_fade();
+            let text_color = Color::new(1.0, 1.0, 1.0, fade);
+
+            // Semi-transparent background
+            let overlay = graphics::Mesh::new_rectangle(
+                ctx,
+                DrawMode::fill(),
+                graphics::Rect::new(0.0, 0.0, SCREEN_WIDTH, SCREEN_HEIGHT),
+                Color::new(0.0, 0.0, 0.0, 0.7 * fade),
+            )?;
+            canvas.draw(&overlay, DrawParam::default());
+
+            // The title sits apart, everything after it is stacked under the final score
+            for (i, line) in self.victory_lines().iter().enumerate() {
+                let y = if i == 0 {
+                    SCREEN_HEIGHT * 0.4
+                } else {
+                    SCREEN_HEIGHT * 0.5 + 20.0 * (i - 1) as f32
+                };
+                self.draw_centered_text(ctx, &mut canvas, line, y, text_color);
+            }
+
+            // Play Again button
+            let button_width = 200.0;
+            let button_height = 50.0;
+            let button_x = SCREEN_WIDTH * 0.5 - button_width * 0.5;
+            let button_y = SCREEN_HEIGHT * 0.6;
+            
+            let button = graphics::Mesh::new_rectangle(
+                ctx,
+                DrawMode::fill(),
+                graphics::Rect::new(button_x, button_y, button_width, button_height),
+                Color::new(0.3, 0.3, 0.8, fade),
+            )?;
+            canvas.draw(&button, DrawParam::default());
+
+            let button_text = graphics::Text::new("Play Again");
+            let text_dims = button_text.dimensions(ctx);
+            canvas.draw(
+                &button_text,
+                DrawParam::default()
+                    .color(text_color)
+                    .dest([
+                        button_x + button_width * 0.5 - text_dims.unwrap().w * 0.5,
+                        button_y + button_height * 0.5 - text_dims.unwrap().h * 0.5,
+                    ]),
+            );
+
+            if self.quit_pending {
+                let y = button_y + button_height + 20.0;
+                self.draw_centered_text(ctx, &mut canvas, "Press cancel again to quit", y, text_color);
+            }
+        }
+
+        canvas.finish(ctx)?;
+        Ok(())
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        input: KeyInput,
+        _repeat: bool,
+    ) -> GameResult {
+        if let Some(action) = input.keycode.and_then(|keycode| MenuAction::from_key(keycode, &self.config)) {
+            if self.handle_menu_action(action) {
+                ctx.request_quit();
+            }
+        }
+
+        match input.keycode {
+            Some(KeyCode::Up) | Some(KeyCode::W) => {
+                self.direction_controller.queue_direction(Direction::Up);
+            }
+            Some(KeyCode::Down) | Some(KeyCode::S) => {
+                self.direction_controller.queue_direction(Direction::Down);
+            }
+            Some(KeyCode::Left) | Some(KeyCode::A) => {
+                self.direction_controller.queue_direction(Direction::Left);
+            }
+            Some(KeyCode::Right) | Some(KeyCode::D) => {
+                self.direction_controller.queue_direction(Direction::Right);
+            }
+            Some(KeyCode::P) => self.toggle_practice_mode(),
+            Some(KeyCode::Back) => self.rewind(),
+            #[cfg(feature = "debug")]
+            Some(KeyCode::F1) => self.show_debug = !self.show_debug,
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn gamepad_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        button: Button,
+        _id: GamepadId,
+    ) -> GameResult {
+        if let Some(action) = MenuAction::from_button(button, &self.config) {
+            if self.handle_menu_action(action) {
+                ctx.request_quit();
+            }
+        }
+        Ok(())
+    }
+
+    fn resize_event(&mut self, _ctx: &mut Context, width: f32, height: f32) -> GameResult {
+        self.fit_to_window(width, height);
+        Ok(())
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult {
+        if button == MouseButton::Left {
+            self.handle_click(Vec2::new(x, y));
+        }
+        Ok(())
+    }
+}
+
+/// Everything a fork or embed can change without editing the game itself
+#[derive(Clone, Debug)]
+pub struct GameConfig {
+    pub app_name: String,
+    pub author: String,
+    pub window_title: String,
+    // Extra directory mounted into ggez's filesystem; ggez's default lookup is used when unset
+    pub assets_dir: Option<PathBuf>,
+    pub window_size: (f32, f32),
+    pub window_fit: WindowFit, // What to do when the window is smaller than the board
+
+    pub starting_score: i32,
+    pub win_dot_fraction: f32, // Share of the dots that must be eaten to win
+    pub edges: Edges,
+    pub corner_cutting: bool, // Start perpendicular turns slightly before the cell center
+    pub dot_intro: bool, // Pop the dots in at round start; play begins once all are shown
+
+    pub score_format: ScoreFormat,
+    pub hud_anchor: [f32; 2], // Top-left corner of the first HUD line
+    pub hud_scale: f32, // Multiplies HUD text size and line spacing
+    pub victory_style: VictoryStyle,
+    pub overlay_fade_duration: f32, // Seconds for end-of-game overlays to fade in
+    pub idle_animation: bool, // Bob Pacman in place until the player commits to a direction
+    pub reduce_motion: bool, // Skips purely decorative animation
+
+    // Confirm plays again on the victory overlay, cancel twice quits
+    pub confirm_keys: Vec<KeyCode>,
+    pub cancel_keys: Vec<KeyCode>,
+    pub confirm_buttons: Vec<Button>,
+    pub cancel_buttons: Vec<Button>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WindowFit {
+    ScaleToFit, // Shrink the board uniformly to fit the window
+    EnforceMinimum, // Grow the window to the board size
+}
+
+// Uniform scale that fits the whole board into a window, never enlarging it
+fn board_fit_scale(window_width: f32, window_height: f32) -> f32 {
+    (window_width / BOARD_SIZE).min(window_height / BOARD_SIZE).min(1.0)
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            app_name: "pacman".to_string(),
+            author: "you".to_string(),
+            window_title: "Pacman".to_string(),
+            assets_dir: None,
+            window_size: (BOARD_SIZE, BOARD_SIZE),
+            window_fit: WindowFit::ScaleToFit,
+            starting_score: 0,
+            win_dot_fraction: 1.0,
+            edges: Edges {
+                top: EdgeBehavior::Clamp,
+                bottom: EdgeBehavior::Clamp,
+                left: EdgeBehavior::Clamp,
+                right: EdgeBehavior::Clamp,
+            },
+            corner_cutting: false,
+            dot_intro: false,
+            score_format: ScoreFormat::Plain,
+            hud_anchor: [10.0, 10.0],
+            hud_scale: 1.0,
+            victory_style: VictoryStyle::Minimal,
+            overlay_fade_duration: 0.4,
+            idle_animation: false,
+            reduce_motion: false,
+            confirm_keys: vec![KeyCode::Return, KeyCode::NumpadEnter],
+            cancel_keys: vec![KeyCode::Escape],
+            confirm_buttons: vec![Button::South, Button::Start],
+            cancel_buttons: vec![Button::East, Button::Select],
+        }
+    }
+}
+
+impl GameConfig {
+    fn validate(&self) -> Result<(), String> {
+        let (width, height) = self.window_size;
+        if !(width > 0.0 && height > 0.0) {
+            return Err(format!("window_size must be positive, got {}x{}", width, height));
+        }
+        if !self.edges.wraps_in_pairs() {
+            return Err("a wrapping edge needs its opposite edge to wrap too".to_string());
+        }
+        Ok(())
+    }
+}
+
+// What `run_with_config` hands to ggez, resolved from a `GameConfig`
+struct LaunchSettings {
+    game_id: String,
+    author: String,
+    window_setup: ggez::conf::WindowSetup,
+    window_mode: ggez::conf::WindowMode,
+    resource_path: Option<PathBuf>,
+    assets_warning: Option<String>, // Printed by `run_with_config`, not while resolving
+}
+
+impl LaunchSettings {
+    fn from_config(config: &GameConfig) -> Self {
+        let (mut width, mut height) = config.window_size;
+        let mut window_mode = ggez::conf::WindowMode::default();
+        // ScaleToFit is applied once the real window size is known
+        if config.window_fit == WindowFit::EnforceMinimum {
+            width = width.max(BOARD_SIZE);
+            height = height.max(BOARD_SIZE);
+            window_mode = window_mode.min_dimensions(BOARD_SIZE, BOARD_SIZE);
+        }
+
+        // Nothing is loaded from the assets directory yet, so a missing one is not fatal
+        let (resource_path, assets_warning) = match resolve_assets_dir(config.assets_dir.as_deref()) {
+            Ok(resource_path) => (resource_path, None),
+            Err(warning) => (None, Some(warning)),
+        };
+
+        Self {
+            game_id: config.app_name.clone(),
+            author: config.author.clone(),
+            window_setup: ggez::conf::WindowSetup::default().title(&config.window_title),
+            window_mode: window_mode.dimensions(width, height),
+            resource_path,
+            assets_warning,
+        }
+    }
+}
+
+// The directory to mount, or why the configured one can't be used
+fn resolve_assets_dir(assets_dir: Option<&Path>) -> Result<Option<PathBuf>, String> {
+    match assets_dir {
+        Some(assets_dir) if !assets_dir.is_dir() => Err(format!(
+            "assets directory {} not found, so it won't be mounted",
+            assets_dir.display()
+        )),
+        assets_dir => Ok(assets_dir.map(Path::to_path_buf)),
+    }
+}
+
+/// Validates `config`, opens the window and runs the game until it quits
+pub fn run_with_config(config: GameConfig) -> GameResult {
+    config.validate().map_err(ggez::GameError::ConfigError)?;
+    let settings = LaunchSettings::from_config(&config);
+    if let Some(warning) = &settings.assets_warning {
+        eprintln!("warning: {}", warning);
+    }
+
+    let mut cb = ggez::ContextBuilder::new(&settings.game_id, &settings.author)
+        .window_setup(settings.window_setup)
+        .window_mode(settings.window_mode);
+    if let Some(resource_path) = settings.resource_path {
+        cb = cb.add_resource_path(resource_path);
+    }
+
+    let (ctx, event_loop) = cb.build()?;
+    let mut state = GameState::new(config);
+    let (width, height) = ctx.gfx.drawable_size();
+    state.fit_to_window(width, height);
+    event::run(ctx, event_loop, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn new_state() -> GameState {
+        GameState::new(GameConfig::default())
+    }
+
+    #[test]
+    fn launch_settings_follow_config() {
+        let config = GameConfig {
+            app_name: "mspacman".to_string(),
+            author: "fork".to_string(),
+            window_title: "Ms. Pacman".to_string(),
+            ..GameConfig::default()
+        };
+        let settings = LaunchSettings::from_config(&config);
+
+        assert_eq!(settings.game_id, "mspacman");
+        assert_eq!(settings.author, "fork");
+        assert_eq!(settings.window_setup.title, "Ms. Pacman");
+        assert_eq!(settings.window_mode.width, BOARD_SIZE);
+        assert_eq!(settings.window_mode.height, BOARD_SIZE);
+    }
+
+    #[test]
+    fn gameplay_options_come_from_config() {
+        let config = GameConfig {
+            starting_score: 500,
+            win_dot_fraction: 0.5,
+            ..GameConfig::default()
+        };
+        let state = GameState::new(config);
+
+        assert_eq!(state.score, 500);
+        assert_eq!(state.dots_required, state.total_dots / 2);
+    }
+
+    #[test]
+    fn score_saturates_instead_of_overflowing() {
+        let mut state = new_state();
+        state.score = i32::MAX - 5;
+        state.add_score(i32::MAX);
+        assert_eq!(state.score, i32::MAX);
+    }
+
+    #[test]
+    fn score_clamps_at_zero() {
+        let mut state = new_state();
+        state.score = 10;
+        state.add_score(-50);
+        assert_eq!(state.score, 0);
+        state.add_score(i32::MIN);
+        assert_eq!(state.score, 0);
+    }
+
+    #[test]
+    fn confirm_on_victory_screen_restarts() {
+        let mut state = new_state();
+        state.game_won = true;
+        state.score = 3240;
+        state.dots.clear();
+
+        let action = MenuAction::from_key(KeyCode::Return, &state.config).unwrap();
+        assert!(!state.handle_menu_action(action));
+        assert!(!state.game_won);
+        assert_eq!(state.score, 0);
+        assert_eq!(state.dots.len(), state.total_dots);
+    }
+
+    #[test]
+    fn cancel_twice_on_victory_screen_requests_quit() {
+        let mut state = new_state();
+        state.game_won = true;
+        let action = MenuAction::from_button(Button::East, &state.config).unwrap();
+        assert!(!state.handle_menu_action(action));
+        assert!(state.quit_pending);
+        assert!(state.handle_menu_action(action));
+    }
+
+    #[test]
+    fn confirm_after_one_cancel_still_restarts() {
+        let mut state = new_state();
+        state.game_won = true;
+        assert!(!state.handle_menu_action(MenuAction::Cancel));
+        assert!(!state.handle_menu_action(MenuAction::Confirm));
+        assert!(!state.game_won);
+        assert!(!state.quit_pending);
+    }
+
+    #[test]
+    fn menu_bindings_come_from_config() {
+        let config = GameConfig {
+            confirm_keys: vec![KeyCode::Space],
+            cancel_keys: vec![KeyCode::Q],
+            confirm_buttons: vec![Button::North],
+            cancel_buttons: Vec::new(),
+            ..GameConfig::default()
+        };
+        assert_eq!(MenuAction::from_key(KeyCode::Space, &config), Some(MenuAction::Confirm));
+        assert_eq!(MenuAction::from_key(KeyCode::Q, &config), Some(MenuAction::Cancel));
+        assert_eq!(MenuAction::from_key(KeyCode::Return, &config), None);
+        assert_eq!(MenuAction::from_button(Button::North, &config), Some(MenuAction::Confirm));
+        assert_eq!(MenuAction::from_button(Button::East, &config), None);
+    }
+
+    #[test]
+    fn menu_actions_are_ignored_during_play() {
+        let mut state = new_state();
+        state.score = 50;
+        assert!(!state.handle_menu_action(MenuAction::Confirm));
+        assert!(!state.handle_menu_action(MenuAction::Cancel));
+        assert_eq!(state.score, 50);
+    }
+
+    // Runs right along a row, asks to turn up just before a cell center and
+    // counts the steps until Pacman reaches the cell two rows above it
+    fn steps_through_turn(corner_cutting: bool) -> u32 {
+        let mut state = GameState::new(GameConfig {
+            corner_cutting,
+            ..GameConfig::default()
+        });
+        state.pacman.pos = Vec2::new(CELL_SIZE, 10.0 * CELL_SIZE);
+        state.direction_controller.queue_direction(Direction::Right);
+        while state.pacman.pos.x < 2.0 * CELL_SIZE - PACMAN_SPEED {
+            state.update(DT);
+        }
+        state.direction_controller.queue_direction(Direction::Up);
+
+        let target = Vec2::new(2.0 * CELL_SIZE, 8.0 * CELL_SIZE);
+        let mut steps = 0;
+        while state.pacman.pos != target {
+            state.update(DT);
+            steps += 1;
+            assert!(steps < 100, "never reached {target}, stuck at {}", state.pacman.pos);
+        }
+        steps
+    }
+
+    #[test]
+    fn corner_cut_covers_less_path_than_square_turn() {
+        assert!(steps_through_turn(true) < steps_through_turn(false));
+    }
+
+    #[test]
+    fn corner_cut_only_applies_to_perpendicular_turns_near_center() {
+        let mut controller = DirectionController::new(true);
+        controller.current_direction = Some(Direction::Right);
+        let near_center = Vec2::new(2.0 * CELL_SIZE - PACMAN_SPEED, 10.0 * CELL_SIZE);
+        let mid_cell = Vec2::new(2.5 * CELL_SIZE, 10.0 * CELL_SIZE);
+
+        assert!(controller.can_cut_corner(Direction::Up, near_center));
+        assert!(!controller.can_cut_corner(Direction::Up, mid_cell));
+        assert!(!controller.can_cut_corner(Direction::Left, near_center));
+
+        controller.corner_cutting = false;
+        assert!(!controller.can_cut_corner(Direction::Up, near_center));
+    }
+
+    #[test]
+    fn rewind_restores_snapshot_exactly() {
+        let mut state = new_state();
+        state.toggle_practice_mode();
+        state.direction_controller.queue_direction(Direction::Right);
+        for _ in 0..PRACTICE_SNAPSHOT_FRAMES + 5 {
+            state.update(DT);
+        }
+        let snapshot = state.history.back().unwrap().clone();
+        assert!(snapshot.score > 0, "the snapshot should be taken mid-run");
+
+        state.direction_controller.queue_direction(Direction::Down);
+        for _ in 0..30 {
+            state.update(DT);
+        }
+        assert!(state.score > snapshot.score);
+
+        state.rewind();
+        assert_eq!(state.pacman.pos, snapshot.pacman.pos);
+        assert_eq!(state.dots, snapshot.dots);
+        assert_eq!(state.score, snapshot.score);
+        assert_eq!(state.direction_controller, snapshot.direction_controller);
+        assert_eq!(state.clock.level, snapshot.round_time);
+    }
+
+    #[test]
+    fn rewind_is_ignored_outside_practice_mode() {
+        let mut state = new_state();
+        state.direction_controller.queue_direction(Direction::Right);
+        for _ in 0..20 {
+            state.update(DT);
+        }
+        let score = state.score;
+        state.rewind();
+        assert_eq!(state.score, score);
+    }
+
+    // Leaves a single dot just ahead of Pacman, who is already moving towards it
+    fn one_dot_from_winning() -> GameState {
+        let mut state = new_state();
+        state.dots = vec![state.pacman.pos + Vec2::new(PACMAN_SPEED * 2.0, 0.0)];
+        state.total_dots = 2;
+        state.dots_required = 2;
+        state.direction_controller.queue_direction(Direction::Right);
+        state
+    }
+
+    #[test]
+    fn mouth_closes_when_game_is_won() {
+        let mut state = one_dot_from_winning();
+        state.pacman.mouth_angle = MAX_MOUTH_ANGLE * 0.5;
+        state.pacman.mouth_opening = true;
+
+        state.update(DT);
+        assert!(state.game_won);
+        assert_eq!(state.pacman.mouth_angle, 0.0);
+        assert!(state.pacman.mouth_opening);
+    }
+
+    #[test]
+    fn dots_required_follows_win_fraction() {
+        assert_eq!(GameState::dots_required(324, 1.0), 324);
+        assert_eq!(GameState::dots_required(324, 0.5), 162);
+        assert_eq!(GameState::dots_required(325, 0.5), 163);
+        // At least one dot must always be eaten
+        assert_eq!(GameState::dots_required(324, 0.0), 1);
+    }
+
+    #[test]
+    fn game_is_won_exactly_at_partial_threshold() {
+        let mut state = new_state();
+        state.dots_required = 5;
+        state.direction_controller.queue_direction(Direction::Right);
+
+        for _ in 0..200 {
+            state.update(DT);
+            assert_eq!(state.game_won, state.dots_eaten() >= state.dots_required);
+            if state.game_won {
+                break;
+            }
+        }
+        assert!(state.game_won);
+        assert_eq!(state.dots_eaten(), 5);
+        assert_eq!(state.dots.len(), state.total_dots - 5);
+    }
+
+    #[test]
+    fn overlay_fades_in_after_winning() {
+        let mut state = one_dot_from_winning();
+        state.update(DT);
+        assert!(state.game_won);
+        assert_eq!(state.overlay_fade(), 0.0);
+
+        let mut previous = state.overlay_fade();
+        while previous < 1.0 {
+            state.update(0.1);
+            let fade = state.overlay_fade();
+            assert!(fade > previous, "fade went from {previous} to {fade}");
+            previous = fade;
+        }
+        for _ in 0..5 {
+            state.update(0.1);
+            assert_eq!(state.overlay_fade(), 1.0);
+        }
+    }
+
+    #[test]
+    fn active_time_excludes_paused_intervals() {
+        let mut clock = Clock::default();
+        clock.advance(1.0, true);
+        clock.advance(2.0, false);
+        clock.advance(0.5, true);
+        assert_eq!(clock.total, 3.5);
+        assert_eq!(clock.active, 1.5);
+
+        clock.start_level();
+        clock.advance(0.25, true);
+        assert_eq!(clock.level, 0.25);
+        assert_eq!(clock.active, 1.75);
+    }
+
+    // Plays a scripted run to victory and returns the frame it was won on
+    fn frames_to_win(dot_intro: bool, dt: f32, inputs: &[(u64, Direction)]) -> (u64, i32, Vec2) {
+        let mut state = GameState::new(GameConfig {
+            win_dot_fraction: 0.07,
+            dot_intro,
+            ..GameConfig::default()
+        });
+        while !state.game_won {
+            assert!(state.frame < 10_000, "scripted run never won");
+            for &(frame, direction) in inputs {
+                if frame == state.frame {
+                    state.direction_controller.queue_direction(direction);
+                }
+            }
+            state.update(dt);
+        }
+        (state.frame, state.score, state.pacman.pos)
+    }
+
+    #[test]
+    fn identical_inputs_win_on_the_same_frame() {
+        let inputs = [(0, Direction::Right), (45, Direction::Down), (90, Direction::Left)];
+        let first = frames_to_win(false, DT, &inputs);
+        assert_eq!(first, frames_to_win(false, DT, &inputs));
+        assert_eq!(first.1, DOT_POINTS * 23);
+
+        // Frame timing must not change when the intro hands over to play
+        let delayed = inputs.map(|(frame, direction)| (frame + DOT_INTRO_FRAMES, direction));
+        let at_60 = frames_to_win(true, DT, &delayed);
+        assert_eq!(at_60, frames_to_win(true, 1.0 / 30.0, &delayed));
+        assert_eq!(at_60, (first.0 + DOT_INTRO_FRAMES, first.1, first.2));
+    }
+
+    #[test]
+    fn idle_bob_only_runs_before_a_direction_is_committed() {
+        let mut state = GameState::new(GameConfig {
+            idle_animation: true,
+            ..GameConfig::default()
+        });
+        for _ in 0..10 {
+            state.update(DT);
+        }
+        assert!(state.idle_animating());
+        let expected = -(state.clock.level * IDLE_BOB_SPEED).sin().abs() * IDLE_BOB_HEIGHT;
+        assert_eq!(state.idle_bob_offset(), expected);
+        assert!(expected < 0.0);
+
+        state.direction_controller.queue_direction(Direction::Right);
+        state.update(DT);
+        assert!(!state.idle_animating());
+        assert_eq!(state.idle_bob_offset(), 0.0);
+    }
+
+    #[test]
+    fn reduce_motion_disables_idle_animation() {
+        let mut state = GameState::new(GameConfig {
+            idle_animation: true,
+            reduce_motion: true,
+            ..GameConfig::default()
+        });
+        for _ in 0..10 {
+            state.update(DT);
+        }
+        assert!(!state.idle_animating());
+        assert_eq!(state.idle_bob_offset(), 0.0);
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn visits_are_counted_per_cell_and_cleared_on_reset() {
+        let mut state = new_state();
+        let start = GameState::cell_index(state.pacman.pos).unwrap();
+        state.record_visit();
+        state.record_visit();
+        assert_eq!(state.visits[start], 2);
+        assert_eq!(state.visits.iter().sum::<u32>(), 2);
+
+        state.reset();
+        assert!(state.visits.iter().all(|&count| count == 0));
+    }
+
+    #[test]
+    fn score_formats() {
+        assert_eq!(ScoreFormat::Plain.format(12300), "12300");
+        assert_eq!(ScoreFormat::Grouped(',').format(12300), "12,300");
+        assert_eq!(ScoreFormat::Grouped(',').format(123), "123");
+        assert_eq!(ScoreFormat::Grouped(' ').format(1234567), "1 234 567");
+        assert_eq!(ScoreFormat::ZeroPadded(6).format(1230), "001230");
+        assert_eq!(ScoreFormat::ZeroPadded(2).format(1230), "1230");
+    }
+
+    #[test]
+    fn edges_clamp_wrap_and_bounce() {
+        use EdgeBehavior::*;
+        let min = CELL_SIZE;
+        let max = CELL_SIZE * (GRID_SIZE - 1) as f32;
+
+        assert_eq!(resolve_edge(300.0, Bounce, Bounce), (300.0, false));
+        assert_eq!(resolve_edge(min - 5.0, Clamp, Wrap), (min, false));
+        assert_eq!(resolve_edge(max + 5.0, Wrap, Clamp), (max, false));
+        assert_eq!(resolve_edge(min - 5.0, Wrap, Clamp), (max, false));
+        assert_eq!(resolve_edge(max + 5.0, Clamp, Wrap), (min, false));
+        assert_eq!(resolve_edge(min - 5.0, Bounce, Clamp), (min, true));
+        assert_eq!(resolve_edge(max + 5.0, Clamp, Bounce), (max, true));
+    }
+
+    #[test]
+    fn one_sided_wrap_is_rejected() {
+        let mut config = GameConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.edges.left = EdgeBehavior::Wrap;
+        assert!(!config.edges.wraps_in_pairs());
+        assert!(config.validate().is_err());
+
+        config.edges.right = EdgeBehavior::Wrap;
+        assert!(config.edges.wraps_in_pairs());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn pacman_wraps_horizontally_and_clamps_vertically() {
+        let mut state = GameState::new(GameConfig {
+            edges: Edges {
+                top: EdgeBehavior::Clamp,
+                bottom: EdgeBehavior::Clamp,
+                left: EdgeBehavior::Wrap,
+                right: EdgeBehavior::Wrap,
+            },
+            ..GameConfig::default()
+        });
+        state.direction_controller.queue_direction(Direction::Left);
+        state.update(DT);
+        assert_eq!(state.pacman.pos, Vec2::new(CELL_SIZE * (GRID_SIZE - 1) as f32, 300.0));
+
+        state.direction_controller.queue_direction(Direction::Up);
+        for _ in 0..200 {
+            state.update(DT);
+        }
+        assert_eq!(state.pacman.pos.y, CELL_SIZE);
+    }
+
+    #[test]
+    fn victory_lines_follow_victory_style() {
+        let mut state = one_dot_from_winning();
+        state.update(DT);
+        assert!(state.game_won);
+        assert_eq!(state.victory_lines(), ["You Won!", "Final Score: 10"]);
+
+        state.config.victory_style = VictoryStyle::Stats;
+        state.clock.level = 12.34;
+        assert_eq!(
+            state.victory_lines(),
+            ["You Won!", "Final Score: 10", "Time: 12.3s", "Dots eaten: 2/2"]
+        );
+    }
+
+    #[test]
+    fn hud_lines_start_at_the_configured_anchor() {
+        assert_eq!(new_state().hud_line_position(0), [10.0, 10.0]);
+
+        let state = GameState::new(GameConfig {
+            hud_anchor: [100.0, 50.0],
+            hud_scale: 2.0,
+            ..GameConfig::default()
+        });
+        assert_eq!(state.hud_line_position(0), [100.0, 50.0]);
+        assert_eq!(state.hud_line_position(1), [100.0, 90.0]);
+    }
+
+    #[test]
+    fn nothing_is_collected_during_dot_intro() {
+        let mut state = GameState::new(GameConfig {
+            dot_intro: true,
+            ..GameConfig::default()
+        });
+        state.direction_controller.queue_direction(Direction::Right);
+        let start = state.pacman.pos;
+        assert!(state.dots.contains(&start));
+
+        for _ in 0..DOT_INTRO_FRAMES {
+            state.update(DT);
+            assert_eq!(state.score, 0);
+            assert_eq!(state.dots.len(), state.total_dots);
+            assert_eq!(state.pacman.pos, start);
+        }
+
+        state.update(DT);
+        assert_eq!(state.score, DOT_POINTS);
+        assert!(!state.dots.contains(&start));
+    }
+
+    #[test]
+    fn reduce_motion_skips_dot_intro() {
+        let state = GameState::new(GameConfig {
+            dot_intro: true,
+            reduce_motion: true,
+            ..GameConfig::default()
+        });
+        assert!(!state.in_intro());
+        assert_eq!(state.dot_reveal(0), 1.0);
+    }
+
+    #[test]
+    fn board_fit_scale_shrinks_but_never_enlarges() {
+        assert_eq!(board_fit_scale(300.0, 450.0), 0.5);
+        assert_eq!(board_fit_scale(BOARD_SIZE, BOARD_SIZE), 1.0);
+        assert_eq!(board_fit_scale(BOARD_SIZE * 2.0, BOARD_SIZE * 3.0), 1.0);
+    }
+
+    #[test]
+    fn small_windows_scale_or_grow_per_window_fit() {
+        let scaled = LaunchSettings::from_config(&GameConfig {
+            window_size: (300.0, 450.0),
+            ..GameConfig::default()
+        });
+        assert_eq!(scaled.window_mode.width, 300.0);
+
+        let grown = LaunchSettings::from_config(&GameConfig {
+            window_size: (300.0, 450.0),
+            window_fit: WindowFit::EnforceMinimum,
+            ..GameConfig::default()
+        });
+        assert_eq!(grown.window_mode.width, BOARD_SIZE);
+        assert_eq!(grown.window_mode.height, BOARD_SIZE);
+        assert_eq!(grown.window_mode.min_width, BOARD_SIZE);
+    }
+
+    #[test]
+    fn view_scale_follows_the_actual_window_size() {
+        let mut state = new_state();
+        state.fit_to_window(300.0, 450.0);
+        assert_eq!(state.view_scale, 0.5);
+        state.fit_to_window(0.0, 0.0);
+        assert_eq!(state.view_scale, 0.5);
+        state.fit_to_window(1920.0, 1080.0);
+        assert_eq!(state.view_scale, 1.0);
+
+        let mut state = GameState::new(GameConfig {
+            window_fit: WindowFit::EnforceMinimum,
+            ..GameConfig::default()
+        });
+        state.fit_to_window(300.0, 450.0);
+        assert_eq!(state.view_scale, 1.0);
+    }
+
+    #[test]
+    fn non_positive_window_sizes_are_rejected() {
+        for window_size in [(0.0, 600.0), (600.0, -1.0), (f32::NAN, 600.0)] {
+            let config = GameConfig {
+                window_size,
+                ..GameConfig::default()
+            };
+            assert!(config.validate().is_err(), "{window_size:?}");
+        }
+    }
+
+    #[test]
+    fn clicks_are_mapped_through_the_view_scale() {
+        let mut state = one_dot_from_winning();
+        state.update(DT);
+        assert!(state.game_won);
+        state.view_scale = 0.5;
+
+        let button_center = Vec2::new(SCREEN_WIDTH * 0.5, SCREEN_HEIGHT * 0.6 + 25.0);
+        state.handle_click(button_center);
+        assert!(state.game_won, "unscaled position is outside the scaled button");
+
+        state.handle_click(button_center * 0.5);
+        assert!(!state.game_won);
+        assert_eq!(state.dots.len(), state.total_dots);
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn debug_line_reports_live_state() {
+        let mut state = new_state();
+        state.direction_controller.queue_direction(Direction::Right);
+        for _ in 0..30 {
+            state.update(0.1);
+        }
+        assert!(state.score > 0);
+        let line = state.debug_line();
+        assert!(line.contains("frame 30 "), "{line}");
+        assert!(line.contains(&format!("score {} ", state.score)), "{line}");
+        assert!(line.contains("round 3.00s"), "{line}");
+        assert!(line.contains("practice off"), "{line}");
+    }
+
+    #[test]
+    fn missing_assets_dir_falls_back_with_a_warning() {
+        assert_eq!(resolve_assets_dir(None), Ok(None));
+
+        let existing = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(resolve_assets_dir(Some(existing)), Ok(Some(existing.to_path_buf())));
+
+        let missing = existing.join("no-such-assets");
+        let warning = resolve_assets_dir(Some(&missing)).unwrap_err();
+        assert!(warning.contains("no-such-assets"), "{warning}");
+
+        let settings = LaunchSettings::from_config(&GameConfig {
+            assets_dir: Some(missing),
+            ..GameConfig::default()
+        });
+        assert_eq!(settings.resource_path, None);
+        assert_eq!(settings.assets_warning, Some(warning));
+    }
+
+    #[test]
+    fn update_loop_keeps_score_dots_and_position_consistent() {
+        let mut state = GameState::new(GameConfig {
+            starting_score: 100,
+            ..GameConfig::default()
+        });
+        let min = CELL_SIZE;
+        let max = CELL_SIZE * (GRID_SIZE - 1) as f32;
+        let turns = [Direction::Right, Direction::Down, Direction::Left, Direction::Up];
+
+        let mut dots = state.dots.len();
+        for step in 0..600 {
+            if step % 37 == 0 {
+                state.direction_controller.queue_direction(turns[step / 37 % turns.len()]);
+            }
+            state.update(DT);
+
+            assert_eq!(state.score, 100 + DOT_POINTS * state.dots_eaten() as i32, "step {step}");
+            assert!(state.dots.len() <= dots, "dots came back at step {step}");
+            dots = state.dots.len();
+            let pos = state.pacman.pos;
+            assert!((min..=max).contains(&pos.x) && (min..=max).contains(&pos.y), "{pos} at step {step}");
+        }
+        assert!(state.dots_eaten() > 0);
+    }
+
+    #[test]
+    fn dot_batch_skips_hidden_dots_and_reuses_its_buffers() {
+        let dots = GameState::spawn_dots();
+        let mut batch = DotBatch::default();
+        batch.rebuild(&dots, |_| 1.0);
+        let fan = DOT_SEGMENTS as usize;
+        assert_eq!(batch.vertices.len(), dots.len() * (fan + 1));
+        assert_eq!(batch.indices.len(), dots.len() * fan * 3);
+
+        let capacity = (batch.vertices.capacity(), batch.indices.capacity());
+        batch.rebuild(&dots, |index| if index % 2 == 0 { 0.5 } else { 0.0 });
+        assert_eq!(batch.indices.len(), dots.len().div_ceil(2) * fan * 3);
+        assert_eq!((batch.vertices.capacity(), batch.indices.capacity()), capacity);
+
+        batch.rebuild(&dots, |_| 0.0);
+        assert!(batch.is_empty());
+    }
+}